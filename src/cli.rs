@@ -0,0 +1,56 @@
+use crate::entry_handler::ItemSource;
+
+/// What to do with the selected entry once the matcher has picked one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Launch the selected entry.
+    Launch,
+    /// Print the selected entry's value to stdout instead of launching it.
+    Print,
+}
+
+/// Parsed command-line arguments for [`crate::run`].
+#[derive(Debug, Clone)]
+pub struct Args {
+    pub source: ItemSource,
+    pub config_path: Option<String>,
+    pub query: Option<String>,
+    pub action: Action,
+}
+
+impl Args {
+    /// Parse launcher flags: `--stdin` selects the stdin item source,
+    /// `--config <path>` picks the config file, `--query <text>` fuzzy-matches
+    /// `<text>` and acts on the best match (without it, desktop mode lists
+    /// every visible entry and stdin mode matches an empty query), and
+    /// `--print` prints the selection rather than launching it. Stdin mode
+    /// always prints, since there's nothing to launch.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Self {
+        let mut source = ItemSource::DesktopEntries;
+        let mut config_path = None;
+        let mut query = None;
+        let mut action = Action::Launch;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--stdin" => source = ItemSource::Stdin,
+                "--print" => action = Action::Print,
+                "--config" => config_path = args.next(),
+                "--query" => query = args.next(),
+                _ => {}
+            }
+        }
+
+        if source == ItemSource::Stdin {
+            action = Action::Print;
+        }
+
+        Args {
+            source,
+            config_path,
+            query,
+            action,
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Scoring weights applied in `AppEntry::compute_score`, tunable via the
+/// `[scoring]` config table without recompiling.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct ScoringConfig {
+    pub name_weight: f64,
+    pub keyword_weight: f64,
+    pub category_weight: f64,
+    /// Half-life, in days, after which the frecency boost's recency portion
+    /// is halved. See `frecency::decay`.
+    pub half_life_days: f64,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        ScoringConfig {
+            name_weight: 5.0,
+            keyword_weight: 1.0,
+            category_weight: 1.0,
+            half_life_days: 30.0,
+        }
+    }
+}
+
+/// `ScoringConfig` as it appears on disk: each weight is optional, so
+/// merging can tell "not set in this file" apart from "explicitly set to a
+/// value that happens to match the default".
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(default)]
+struct RawScoringConfig {
+    name_weight: Option<f64>,
+    keyword_weight: Option<f64>,
+    category_weight: Option<f64>,
+    half_life_days: Option<f64>,
+}
+
+impl RawScoringConfig {
+    /// Merge `other` on top of `self` field by field: a field `other`
+    /// leaves unset falls back to `self`'s value for that field.
+    fn merge(self, other: RawScoringConfig) -> RawScoringConfig {
+        RawScoringConfig {
+            name_weight: other.name_weight.or(self.name_weight),
+            keyword_weight: other.keyword_weight.or(self.keyword_weight),
+            category_weight: other.category_weight.or(self.category_weight),
+            half_life_days: other.half_life_days.or(self.half_life_days),
+        }
+    }
+
+    /// Fill in any still-unset fields with `ScoringConfig`'s defaults.
+    fn resolve(self) -> ScoringConfig {
+        let defaults = ScoringConfig::default();
+        ScoringConfig {
+            name_weight: self.name_weight.unwrap_or(defaults.name_weight),
+            keyword_weight: self.keyword_weight.unwrap_or(defaults.keyword_weight),
+            category_weight: self.category_weight.unwrap_or(defaults.category_weight),
+            half_life_days: self.half_life_days.unwrap_or(defaults.half_life_days),
+        }
+    }
+}
+
+/// `Config` as it appears on disk, before system/user layers are merged and
+/// scoring defaults are resolved.
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(default)]
+struct RawConfig {
+    directories: Vec<String>,
+    scoring: RawScoringConfig,
+}
+
+impl RawConfig {
+    /// Merge `other` on top of `self`: `directories` is appended (system
+    /// defaults plus user additions, not a replacement), while `scoring` is
+    /// merged field by field so a user config that only sets one weight
+    /// doesn't discard the system config's other weights.
+    fn merge(mut self, other: RawConfig) -> RawConfig {
+        self.directories.extend(other.directories);
+        self.scoring = self.scoring.merge(other.scoring);
+        self
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    pub directories: Vec<String>,
+    pub scoring: ScoringConfig,
+}
+
+/// The launcher's XDG config directory (`$XDG_CONFIG_HOME/launcher`, or
+/// `$HOME/.config/launcher`).
+pub fn config_dir() -> PathBuf {
+    if let Ok(xdg_config) = env::var("XDG_CONFIG_HOME") {
+        PathBuf::from(xdg_config).join(Path::new("launcher"))
+    } else if let Ok(home) = env::var("HOME") {
+        PathBuf::from(home).join(".config").join("launcher")
+    } else {
+        panic!("Neither XDG_CONFIG_HOME nor HOME is set");
+    }
+}
+
+/// The system-wide default config location, read before the user's own
+/// config.
+fn system_config_path() -> PathBuf {
+    PathBuf::from("/etc/launcher/config.toml")
+}
+
+fn read_config_file(path: &Path) -> Result<RawConfig, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(RawConfig::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    toml::from_str(&content)
+        .map_err(|err| format!("failed to parse config at {}: {err}", path.display()).into())
+}
+
+/// Apply `LAUNCHER_*` environment variable overrides on top of the merged
+/// file config. `LAUNCHER_DIRECTORIES` is a `:`-separated list of extra
+/// directories, appended after whatever the config files already set.
+fn apply_env_overrides(mut config: RawConfig) -> RawConfig {
+    if let Ok(directories) = env::var("LAUNCHER_DIRECTORIES") {
+        config.directories.extend(
+            env::split_paths(&directories).map(|dir| dir.to_string_lossy().into_owned()),
+        );
+    }
+
+    config
+}
+
+/// Layered config load, in the spirit of cargo's config system: the system
+/// default, then the user's config (an explicit `path_string` standing in
+/// for `$XDG_CONFIG_HOME/launcher/config.toml`), merged field by field, with
+/// `LAUNCHER_*` environment variables applied last.
+pub fn load_config(path_string: Option<String>) -> Result<Config, Box<dyn std::error::Error>> {
+    let system = read_config_file(&system_config_path())?;
+
+    let user_path = match path_string {
+        Some(path) => {
+            println!("Path loaded: {}", path);
+            let path = PathBuf::from(path);
+            if path.is_dir() {
+                path.join("config.toml")
+            } else {
+                path
+            }
+        }
+        None => config_dir().join("config.toml"),
+    };
+    let user = read_config_file(&user_path)?;
+
+    let merged = apply_env_overrides(system.merge(user));
+    Ok(Config {
+        directories: merged.directories,
+        scoring: merged.scoring.resolve(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A user config that only overrides `keyword_weight` must not clobber
+    /// the system config's `category_weight` override — regression test for
+    /// a wholesale-replace bug where merging compared the whole struct
+    /// instead of merging field by field.
+    #[test]
+    fn scoring_merge_keeps_unset_fields_from_system_config() {
+        let system = RawScoringConfig {
+            name_weight: None,
+            keyword_weight: None,
+            category_weight: Some(7.0),
+            half_life_days: None,
+        };
+        let user = RawScoringConfig {
+            name_weight: None,
+            keyword_weight: Some(2.0),
+            category_weight: None,
+            half_life_days: None,
+        };
+
+        let merged = system.merge(user);
+
+        assert_eq!(merged.category_weight, Some(7.0));
+        assert_eq!(merged.keyword_weight, Some(2.0));
+    }
+
+    #[test]
+    fn scoring_merge_lets_user_config_override_system_config() {
+        let system = RawScoringConfig {
+            name_weight: Some(1.0),
+            ..RawScoringConfig::default()
+        };
+        let user = RawScoringConfig {
+            name_weight: Some(9.0),
+            ..RawScoringConfig::default()
+        };
+
+        assert_eq!(system.merge(user).name_weight, Some(9.0));
+    }
+}
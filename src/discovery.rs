@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use freedesktop_file_parser::{DesktopFile, parse};
+
+/// A discovered `.desktop` file together with the identity it was found
+/// under: its desktop file ID (for frecency lookups) and the path it was
+/// parsed from (needed to expand the `%k` Exec field code).
+#[derive(Debug)]
+pub struct DiscoveredEntry {
+    pub id: String,
+    pub path: PathBuf,
+    pub desktop_file: DesktopFile,
+}
+
+/// Discover `.desktop` files following the Desktop Entry Specification:
+/// user-configured directories take highest precedence, followed by
+/// `$XDG_DATA_HOME/applications`, then each `$XDG_DATA_DIRS/applications`
+/// entry (defaulting to `/usr/share:/usr/local/share`). When the same
+/// desktop file ID appears under more than one root, the highest-precedence
+/// copy wins and the rest are discarded.
+pub fn discover_desktop_entries(
+    extra_directories: &[String],
+) -> Result<Vec<DiscoveredEntry>, Box<dyn std::error::Error>> {
+    let mut seen_ids: HashSet<String> = HashSet::new();
+    let mut entries = Vec::new();
+
+    for root in applications_dirs(extra_directories) {
+        for discovered in scan_applications_dir(&root)? {
+            if !seen_ids.insert(discovered.id.clone()) {
+                continue;
+            }
+            entries.push(discovered);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Build the ordered list of `applications` directories to scan, highest
+/// precedence first.
+fn applications_dirs(extra_directories: &[String]) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = extra_directories.iter().map(PathBuf::from).collect();
+
+    let data_home = env::var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let home = env::var("HOME").expect("neither XDG_DATA_HOME nor HOME is set");
+            PathBuf::from(home).join(".local/share")
+        });
+    dirs.push(data_home.join("applications"));
+
+    let data_dirs =
+        env::var("XDG_DATA_DIRS").unwrap_or_else(|_| "/usr/share:/usr/local/share".to_string());
+    for dir in env::split_paths(&data_dirs) {
+        dirs.push(dir.join("applications"));
+    }
+
+    dirs
+}
+
+/// Recursively scan `root` for `.desktop` files.
+fn scan_applications_dir(root: &Path) -> Result<Vec<DiscoveredEntry>, Box<dyn std::error::Error>> {
+    let mut found = Vec::new();
+
+    if root.is_dir() {
+        walk_applications_dir(root, root, &mut found)?;
+    }
+
+    Ok(found)
+}
+
+fn walk_applications_dir(
+    root: &Path,
+    dir: &Path,
+    found: &mut Vec<DiscoveredEntry>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            walk_applications_dir(root, &path, found)?;
+            continue;
+        }
+
+        if path.extension() != Some(OsStr::new("desktop")) {
+            continue;
+        }
+
+        let mut contents = String::new();
+        File::open(&path)?.read_to_string(&mut contents)?;
+        let desktop_file = parse(&contents).unwrap();
+
+        found.push(DiscoveredEntry {
+            id: desktop_file_id(root, &path),
+            path,
+            desktop_file,
+        });
+    }
+
+    Ok(())
+}
+
+/// The desktop file ID is the path relative to the `applications` root,
+/// with the `.desktop` extension stripped and `/` replaced by `-`.
+fn desktop_file_id(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .with_extension("")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_desktop_file(applications_dir: &Path, file_name: &str, name: &str) {
+        fs::create_dir_all(applications_dir).unwrap();
+        fs::write(
+            applications_dir.join(file_name),
+            format!("[Desktop Entry]\nType=Application\nName={name}\nExec=foo\n"),
+        )
+        .unwrap();
+    }
+
+    fn scratch_dir(label: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "launcher-discovery-test-{label}-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ))
+    }
+
+    /// When the same desktop file ID is found under more than one root, the
+    /// copy from the highest-precedence root (here, a user-configured
+    /// directory) must win, and the lower-precedence `XDG_DATA_HOME` copy
+    /// must be dropped rather than appearing as a second entry.
+    #[test]
+    fn higher_precedence_root_wins_on_duplicate_id() {
+        let base = scratch_dir("precedence");
+        let user_dir = base.join("user-apps");
+        let data_home = base.join("data-home");
+
+        write_desktop_file(&user_dir, "app.desktop", "User version");
+        write_desktop_file(&data_home.join("applications"), "app.desktop", "Data-home version");
+
+        unsafe {
+            std::env::set_var("XDG_DATA_HOME", &data_home);
+            std::env::set_var("XDG_DATA_DIRS", base.join("no-such-dir"));
+        }
+
+        let result =
+            discover_desktop_entries(&[user_dir.to_string_lossy().into_owned()]).unwrap();
+
+        unsafe {
+            std::env::remove_var("XDG_DATA_HOME");
+            std::env::remove_var("XDG_DATA_DIRS");
+        }
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "app");
+        assert_eq!(result[0].desktop_file.entry.name.default, "User version");
+    }
+}
@@ -1,23 +1,45 @@
-use std::{
-    borrow::Cow,
-    ffi::OsStr,
-    fs::{self, File},
-    io::Read,
-};
+use std::{borrow::Cow, io};
 
-use freedesktop_file_parser::{DesktopFile, EntryType, parse};
+use freedesktop_file_parser::EntryType;
 use nucleo_matcher::{
     Matcher, Utf32Str,
     pattern::{CaseMatching, Normalization, Pattern},
 };
 
-#[derive(Debug)]
+use crate::config::ScoringConfig;
+use crate::discovery::DiscoveredEntry;
+use crate::frecency::UsageStore;
+
+/// Weight applied to the frecency boost relative to a raw nucleo match
+/// score, so history can surface an app even on an empty or short query.
+const FRECENCY_WEIGHT: f64 = 10f64;
+
+/// Where the items fed into the fuzzy matcher come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemSource {
+    /// Enumerate `.desktop` files (the default launcher behaviour).
+    DesktopEntries,
+    /// Read newline-delimited lines from stdin, dmenu-style.
+    Stdin,
+}
+
+#[derive(Debug, Clone)]
 pub struct AppEntry<'a> {
     pub name: Cow<'a, str>,
     pub exec: Cow<'a, str>,
     pub keywords: Vec<Cow<'a, str>>,
     pub categories: Vec<Cow<'a, str>>,
 
+    /// The entry's own name, as opposed to `name`'s UI display label (which
+    /// for a desktop Action is `"{app} – {action}"`). This is what `%c`
+    /// should expand to, per the Desktop Entry Specification.
+    pub exec_name: Cow<'a, str>,
+
+    /// Stable key used to look up this entry's launch history (a desktop
+    /// file ID for app entries, `"{id}::{action key}"` for action entries,
+    /// the line prefixed with `"stdin:"` for stdin entries).
+    pub id: Cow<'a, str>,
+
     // Cached UTF-32 data (for fuzzy finding)
     name_buffer: Vec<char>,
     keywords_buffers: Vec<Vec<char>>,
@@ -33,6 +55,42 @@ pub struct LauncherState<'a> {
     pub results: Vec<AppEntry<'a>>,
 }
 
+impl<'a> LauncherState<'a> {
+    pub fn new(entries: Vec<AppEntry<'a>>) -> Self {
+        LauncherState {
+            entries,
+            query: String::new(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Re-run the fuzzy matching pipeline against `query`, refreshing `results`
+    /// in descending score order. `scoring` supplies the name/keyword/category
+    /// weights, and `usage` folds each entry's frecency (launch frequency and
+    /// recency as of `now`, unix seconds) into its score, so frequently/
+    /// recently launched entries float up even on a short query.
+    pub fn set_query(
+        &mut self,
+        query: &str,
+        scoring: &ScoringConfig,
+        usage: &UsageStore,
+        now: u64,
+    ) {
+        self.query = query.to_string();
+
+        let mut matcher = Matcher::new(nucleo_matcher::Config::DEFAULT);
+        let pattern = Pattern::parse(&self.query, CaseMatching::Ignore, Normalization::Smart);
+
+        let mut results = self.entries.clone();
+        for entry in &mut results {
+            entry.compute_score(&mut matcher, &pattern, scoring, usage, now);
+        }
+        results.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+
+        self.results = results;
+    }
+}
+
 impl<'a> Default for AppEntry<'_> {
     fn default() -> Self {
         Self {
@@ -40,6 +98,8 @@ impl<'a> Default for AppEntry<'_> {
             exec: Cow::Borrowed(""),
             keywords: Vec::new(),
             categories: Vec::new(),
+            exec_name: Cow::Borrowed(""),
+            id: Cow::Borrowed(""),
 
             name_buffer: Vec::new(),
             keywords_buffers: Vec::new(),
@@ -51,27 +111,37 @@ impl<'a> Default for AppEntry<'_> {
 }
 
 impl<'a> AppEntry<'_> {
-    pub fn new<N, E, K, C>(name: N, exec: E, keywords: Vec<K>, categories: Vec<C>) -> AppEntry<'a>
+    pub fn new<N, E, K, C, I>(
+        name: N,
+        exec: E,
+        keywords: Vec<K>,
+        categories: Vec<C>,
+        id: I,
+    ) -> AppEntry<'a>
     where
         N: Into<Cow<'a, str>>,
         E: Into<Cow<'a, str>>,
         K: Into<Cow<'a, str>>,
         C: Into<Cow<'a, str>>,
+        I: Into<Cow<'a, str>>,
     {
         let name: Cow<'a, str> = name.into();
         let exec: Cow<'a, str> = exec.into();
         let keywords: Vec<Cow<'a, str>> = keywords.into_iter().map(|k| k.into()).collect();
         let categories: Vec<Cow<'a, str>> = categories.into_iter().map(|c| c.into()).collect();
+        let id: Cow<'a, str> = id.into();
 
         let name_buffer = name.chars().collect();
         let keywords_buffers = keywords.iter().map(|k| k.chars().collect()).collect();
         let categories_buffers = categories.iter().map(|c| c.chars().collect()).collect();
 
         AppEntry {
+            exec_name: name.clone(),
             name: name,
             exec: exec,
             keywords: keywords,
             categories: categories,
+            id: id,
 
             name_buffer: name_buffer,
             keywords_buffers: keywords_buffers,
@@ -103,61 +173,101 @@ impl<'a> AppEntry<'_> {
             .into_iter()
     }
 
-    pub fn compute_score(&mut self, matcher: &mut Matcher, pattern: &Pattern) {
+    pub fn compute_score(
+        &mut self,
+        matcher: &mut Matcher,
+        pattern: &Pattern,
+        scoring: &ScoringConfig,
+        usage: &UsageStore,
+        now: u64,
+    ) {
         // TODO add caching
         let mut total_score = 0f64;
 
         if let Some(score) = pattern.score(self.name_utf32(), matcher) {
-            total_score += score as f64 * 5f64;
+            total_score += score as f64 * scoring.name_weight;
         }
 
         for keyword in self.keywords_utf32() {
             if let Some(score) = pattern.score(keyword, matcher) {
-                total_score += score as f64;
+                total_score += score as f64 * scoring.keyword_weight;
+            }
+        }
+
+        for category in self.categories_utf32() {
+            if let Some(score) = pattern.score(category, matcher) {
+                total_score += score as f64 * scoring.category_weight;
             }
         }
 
+        total_score += usage.frecency(&self.id, now, scoring.half_life_days) * FRECENCY_WEIGHT;
+
         self.score = Some(total_score as i64);
     }
 }
 
-fn get_desktop_entries(directory: &str) -> Result<Vec<DesktopFile>, Box<dyn std::error::Error>> {
-    let mut desktop_entries: Vec<DesktopFile> = Vec::new();
-    let files = fs::read_dir(directory).unwrap();
-
-    for file in files {
-        let path = file.unwrap().path();
-        let extension = path.extension();
+/// Read newline-delimited lines from stdin into `AppEntry` records, for use
+/// as a generic dmenu-style picker. The line text is used verbatim as both
+/// the displayed name and the value printed on selection, but its frecency
+/// `id` is namespaced with a `"stdin:"` prefix so an unrelated stdin picker
+/// invocation can never alias a desktop file ID and pollute its score.
+pub fn read_stdin_entries() -> Result<Vec<AppEntry<'static>>, Box<dyn std::error::Error>> {
+    let mut entries = Vec::new();
 
-        if extension == Some(OsStr::new("desktop")) {
-            let mut file_buffer = File::open(path)?;
-            let mut contents = String::new();
-            file_buffer.read_to_string(&mut contents)?;
-
-            let desktop_file = parse(&contents).unwrap();
-            desktop_entries.push(desktop_file);
-        }
+    for line in io::stdin().lines() {
+        let line = line?;
+        entries.push(AppEntry::new(
+            line.clone(),
+            line.clone(),
+            Vec::<String>::new(),
+            Vec::<String>::new(),
+            format!("stdin:{line}"),
+        ));
     }
 
-    Ok(desktop_entries)
+    Ok(entries)
 }
 
-fn parse_desktop_entries(
-    desktop_entries: &Vec<DesktopFile>,
+pub fn parse_desktop_entries(
+    discovered: &[DiscoveredEntry],
 ) -> Result<Vec<AppEntry>, Box<dyn std::error::Error>> {
-    let app_entries: Vec<AppEntry> = desktop_entries
+    let app_entries: Vec<AppEntry> = discovered
         .iter()
-        .filter_map(|entry| {
-            if let EntryType::Application(app) = &entry.entry.entry_type {
-                let mut new_entry = AppEntry::default();
+        .flat_map(|discovered| {
+            let entry = &discovered.desktop_file;
+            let mut entries = Vec::new();
+
+            let EntryType::Application(app) = &entry.entry.entry_type else {
+                return entries;
+            };
+
+            let Some(exec) = app.exec.as_ref() else {
+                return entries;
+            };
 
-                new_entry.name = <String as AsRef<str>>::as_ref(&entry.entry.name.default).into();
-                new_entry.exec = app.exec.as_ref()?.into();
+            let name: Cow<str> = <String as AsRef<str>>::as_ref(&entry.entry.name.default).into();
 
-                Some(new_entry)
-            } else {
-                None
+            let mut base_entry = AppEntry::default();
+            base_entry.name = name.clone();
+            base_entry.exec = exec.as_str().into();
+            base_entry.exec_name = name.clone();
+            base_entry.id = discovered.id.clone().into();
+            entries.push(base_entry);
+
+            for (action_key, action) in &entry.actions {
+                let Some(action_exec) = action.exec.as_ref() else {
+                    continue;
+                };
+
+                let mut action_entry = AppEntry::default();
+                action_entry.name = format!("{} – {}", name, action.name.default).into();
+                action_entry.exec = action_exec.as_str().into();
+                action_entry.exec_name = action.name.default.clone().into();
+                action_entry.id = format!("{}::{}", discovered.id, action_key).into();
+                entries.push(action_entry);
             }
+
+            entries
         })
         .collect();
 
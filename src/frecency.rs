@@ -0,0 +1,131 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+const HOUR_SECS: u64 = 3600;
+const DAY_SECS: u64 = 24 * HOUR_SECS;
+const WEEK_SECS: u64 = 7 * DAY_SECS;
+const MONTH_SECS: u64 = 30 * DAY_SECS;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UsageRecord {
+    count: u64,
+    last_launched: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UsageData {
+    #[serde(default)]
+    entries: HashMap<String, UsageRecord>,
+}
+
+/// Persisted per-item launch history (keyed by each entry's `AppEntry::id`:
+/// a desktop file ID, or a `"stdin:"`-prefixed value for stdin items), used
+/// to rank frequently/recently launched
+/// items above a plain name/keyword match.
+#[derive(Debug)]
+pub struct UsageStore {
+    path: PathBuf,
+    data: UsageData,
+}
+
+impl UsageStore {
+    /// Load the usage store from `path`, starting empty if the file is
+    /// missing or fails to parse.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let data = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default();
+
+        UsageStore { path, data }
+    }
+
+    /// Record a launch of `key` at `now` (unix seconds).
+    pub fn record_launch(&mut self, key: &str, now: u64) {
+        let record = self.data.entries.entry(key.to_string()).or_default();
+        record.count += 1;
+        record.last_launched = now;
+    }
+
+    /// The frecency boost for `key` at `now` (unix seconds), combining
+    /// launch frequency with an exponential decay of recency over
+    /// `half_life_days`. Items never launched contribute nothing.
+    pub fn frecency(&self, key: &str, now: u64, half_life_days: f64) -> f64 {
+        let Some(record) = self.data.entries.get(key) else {
+            return 0.0;
+        };
+
+        let elapsed = now.saturating_sub(record.last_launched);
+        let half_life_secs = (half_life_days * DAY_SECS as f64) as u64;
+        record.count as f64 * decay(elapsed, half_life_secs)
+    }
+
+    /// Write the store back to disk, creating the parent directory if
+    /// needed.
+    pub fn flush(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = toml::to_string_pretty(&self.data)?;
+        fs::write(&self.path, content)?;
+
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Classify `elapsed_secs` into the hour/day/week/month buckets a launch
+/// history is naturally read in, then evaluate the half-life decay curve at
+/// that bucket's representative age. Bucketing avoids the score jittering
+/// between near-identical ages while keeping the curve itself exponential.
+fn decay(elapsed_secs: u64, half_life_secs: u64) -> f64 {
+    let representative_age = match elapsed_secs {
+        e if e < HOUR_SECS => HOUR_SECS / 2,
+        e if e < DAY_SECS => DAY_SECS / 2,
+        e if e < WEEK_SECS => WEEK_SECS / 2,
+        e if e < MONTH_SECS => MONTH_SECS / 2,
+        e => e,
+    };
+
+    0.5f64.powf(representative_age as f64 / half_life_secs as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decay_at_zero_elapsed_is_near_full_strength() {
+        // Even a launch seconds ago falls in the hour bucket, whose
+        // representative age is half an hour, not zero.
+        let score = decay(0, MONTH_SECS);
+        assert!(score > 0.9 && score <= 1.0);
+    }
+
+    #[test]
+    fn decay_at_half_life_is_one_half() {
+        let score = decay(MONTH_SECS, MONTH_SECS);
+        assert!((score - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decay_shrinks_with_age() {
+        assert!(decay(DAY_SECS, MONTH_SECS) > decay(WEEK_SECS, MONTH_SECS));
+        assert!(decay(WEEK_SECS, MONTH_SECS) > decay(MONTH_SECS, MONTH_SECS));
+    }
+
+    #[test]
+    fn decay_shrinks_with_shorter_half_life() {
+        assert!(decay(WEEK_SECS, MONTH_SECS) > decay(WEEK_SECS, WEEK_SECS));
+    }
+}
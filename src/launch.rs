@@ -0,0 +1,171 @@
+use std::{
+    env,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use freedesktop_file_parser::ApplicationEntry;
+
+/// Parse an `Exec=` value into argv tokens per the Desktop Entry
+/// Specification's quoting rules: whitespace separates tokens outside
+/// double quotes, and a backslash inside double quotes escapes the next
+/// character rather than being taken literally.
+fn tokenize_exec(exec: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    let mut chars = exec.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            '\\' if in_quotes => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+
+    if has_token {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Expand Exec field codes for a launch with no file/URL argument supplied.
+/// `%f`/`%u`/`%F`/`%U` are dropped outright since there's nothing to fill
+/// them with, `%i` becomes `--icon <Icon>` when the entry has an icon,
+/// `%c` becomes `name` (the entry's own name, not its display label), and
+/// `%k` becomes the path to the
+/// `.desktop` file itself.
+fn expand_field_codes(
+    tokens: &[String],
+    name: &str,
+    icon: Option<&str>,
+    desktop_file_path: &Path,
+) -> Vec<String> {
+    let mut expanded = Vec::new();
+
+    for token in tokens {
+        match token.as_str() {
+            "%f" | "%u" | "%F" | "%U" => {}
+            "%i" => {
+                if let Some(icon) = icon {
+                    expanded.push("--icon".to_string());
+                    expanded.push(icon.to_string());
+                }
+            }
+            "%c" => expanded.push(name.to_string()),
+            "%k" => expanded.push(desktop_file_path.to_string_lossy().into_owned()),
+            _ => expanded.push(token.clone()),
+        }
+    }
+
+    expanded
+}
+
+/// Whether `binary` resolves to an executable file, either directly (if
+/// given as an absolute path) or by searching `$PATH`.
+fn is_on_path(binary: &str) -> bool {
+    let path = Path::new(binary);
+    if path.is_absolute() {
+        return path.is_file();
+    }
+
+    env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).any(|dir| dir.join(binary).is_file()))
+        .unwrap_or(false)
+}
+
+/// The terminal emulator used to wrap `Terminal=true` entries, read from
+/// `$TERMINAL` with a common fallback.
+fn terminal_emulator() -> String {
+    env::var("TERMINAL").unwrap_or_else(|_| "xterm".to_string())
+}
+
+/// Launch `exec` (the entry's own `Exec`, or an action's): expand its field
+/// codes, honor `app`'s `TryExec` and `Terminal`, and spawn it detached from
+/// the launcher. `app` is always the parent application entry, even when
+/// `exec` comes from one of its actions, since actions don't carry their own
+/// `TryExec`/`Terminal`.
+pub fn launch(
+    app: &ApplicationEntry,
+    exec: &str,
+    name: &str,
+    desktop_file_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(try_exec) = app.try_exec.as_ref()
+        && !is_on_path(try_exec)
+    {
+        return Err(format!("TryExec binary not found on PATH: {try_exec}").into());
+    }
+
+    let tokens = tokenize_exec(exec);
+    let mut argv = expand_field_codes(&tokens, name, app.icon.as_deref(), desktop_file_path);
+
+    if argv.is_empty() {
+        return Err("Exec expanded to an empty command".into());
+    }
+
+    if app.terminal.unwrap_or(false) {
+        let mut wrapped = vec![terminal_emulator(), "-e".to_string()];
+        wrapped.append(&mut argv);
+        argv = wrapped;
+    }
+
+    let program = argv.remove(0);
+
+    Command::new(program)
+        .args(argv)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tokenize_exec;
+
+    #[test]
+    fn tokenize_exec_splits_on_whitespace() {
+        assert_eq!(
+            tokenize_exec("firefox --new-window %u"),
+            vec!["firefox", "--new-window", "%u"]
+        );
+    }
+
+    #[test]
+    fn tokenize_exec_keeps_quoted_whitespace_together() {
+        assert_eq!(
+            tokenize_exec(r#"sh -c "echo hello world""#),
+            vec!["sh", "-c", "echo hello world"]
+        );
+    }
+
+    #[test]
+    fn tokenize_exec_honors_backslash_escapes_inside_quotes() {
+        assert_eq!(
+            tokenize_exec(r#"sh -c "say \"hi\"""#),
+            vec!["sh", "-c", r#"say "hi""#]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use freedesktop_file_parser::{DesktopFile, EntryType};
+
+use crate::cli::Action;
+use crate::config::{config_dir, load_config};
+use crate::discovery::discover_desktop_entries;
+use crate::entry_handler::{ItemSource, parse_desktop_entries, read_stdin_entries};
+use crate::frecency::UsageStore;
+
+pub mod cli;
+pub mod config;
+pub mod discovery;
+pub mod entry_handler;
+pub mod frecency;
+pub mod launch;
+
+pub use cli::Args;
+pub use entry_handler::{AppEntry, LauncherState};
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn show_desktop_file(desktop_file: &DesktopFile) {
+    println!("Name: {}", desktop_file.entry.name.default);
+    println!("  Type: {}", desktop_file.entry.entry_type);
+
+    if let EntryType::Application(app) = &desktop_file.entry.entry_type {
+        if let Some(exec) = app.exec.as_ref() {
+            println!("  Exec: {}", exec);
+        }
+        if let Some(path) = app.path.as_ref() {
+            println!("  Path: {}", path);
+        }
+        if let Some(keywords) = app.keywords.as_ref() {
+            println!("  Keywords: {:?}", keywords.default);
+        }
+
+        if let Some(categories) = app.categories.as_ref()
+            && !categories.is_empty()
+        {
+            println!("  -- Categories --");
+            for category in categories {
+                println!("    {}", category);
+            }
+        }
+    }
+
+    let actions = &desktop_file.actions;
+    if !actions.is_empty() {
+        println!("  -- Actions --");
+        for action in actions.values() {
+            println!("    Action: {}", action.name.default);
+            if let Some(exec) = &action.exec {
+                println!("    Action command: {}", exec);
+            }
+        }
+    }
+}
+
+fn run_stdin_picker(
+    config_path: Option<String>,
+    query: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = load_config(config_path)?;
+    let mut usage = UsageStore::load(config_dir().join("usage.toml"));
+    let now = unix_now();
+
+    let entries = read_stdin_entries()?;
+    let mut state = LauncherState::new(entries);
+    state.set_query(query, &config.scoring, &usage, now);
+
+    if let Some(selected) = state.results.first() {
+        println!("{}", selected.name);
+        usage.record_launch(&selected.id, now);
+        usage.flush()?;
+    }
+
+    Ok(())
+}
+
+/// With no query, list every visible desktop entry (the launcher's original
+/// behaviour). With a query, fuzzy-match and act on the best entry per
+/// `action`.
+fn run_desktop_picker(
+    config_path: Option<String>,
+    query: Option<&str>,
+    action: Action,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = load_config(config_path)?;
+    let catalog = discover_desktop_entries(&config.directories)?;
+
+    let Some(query) = query else {
+        for discovered in &catalog {
+            let desktop_file = &discovered.desktop_file;
+            if desktop_file.entry.no_display.unwrap_or(false) {
+                continue;
+            }
+
+            show_desktop_file(desktop_file);
+            println!();
+        }
+
+        return Ok(());
+    };
+
+    let mut usage = UsageStore::load(config_dir().join("usage.toml"));
+    let now = unix_now();
+
+    let entries = parse_desktop_entries(&catalog)?;
+    let mut state = LauncherState::new(entries);
+    state.set_query(query, &config.scoring, &usage, now);
+
+    let Some(selected) = state.results.first() else {
+        return Ok(());
+    };
+
+    if action == Action::Print {
+        println!("{}", selected.name);
+        return Ok(());
+    }
+
+    let discovered = catalog
+        .iter()
+        .find(|discovered| {
+            // Action entries carry `"{app id}::{action key}"` as their id.
+            selected
+                .id
+                .strip_prefix(discovered.id.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+        .ok_or("matched entry vanished from the catalog")?;
+
+    if let EntryType::Application(app) = &discovered.desktop_file.entry.entry_type {
+        launch::launch(app, &selected.exec, &selected.exec_name, &discovered.path)?;
+        usage.record_launch(&selected.id, now);
+        usage.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Run the launcher end-to-end with already-parsed arguments: discover or
+/// read entries per `args.source`, fuzzy-match `args.query` against them,
+/// and print or launch the best match per `args.action`.
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    match args.source {
+        ItemSource::Stdin => {
+            run_stdin_picker(args.config_path, args.query.as_deref().unwrap_or(""))
+        }
+        ItemSource::DesktopEntries => {
+            run_desktop_picker(args.config_path, args.query.as_deref(), args.action)
+        }
+    }
+}
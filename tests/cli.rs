@@ -0,0 +1,59 @@
+//! Smoke test for the whole stdin-picker pipeline, driven through the real
+//! binary the way a user would invoke it: `launcher --stdin --query <text>`.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// A throwaway `$HOME` so the test doesn't touch the real user's usage
+/// history, removed again on drop.
+struct TempHome(PathBuf);
+
+impl TempHome {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "launcher-cli-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        TempHome(dir)
+    }
+}
+
+impl Drop for TempHome {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn stdin_query_prints_best_match() {
+    let home = TempHome::new();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_launcher"))
+        .args(["--stdin", "--query", "firefox"])
+        .env("HOME", &home.0)
+        .env_remove("XDG_CONFIG_HOME")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn launcher binary");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"Firefox Web Browser\nGIMP Image Editor\nVLC Media Player\n")
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "Firefox Web Browser"
+    );
+}